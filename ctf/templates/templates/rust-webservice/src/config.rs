@@ -0,0 +1,56 @@
+//! Layered configuration: built-in defaults, an optional `config.toml`,
+//! environment variables (`CTF_*`, loaded via `dotenv`), and finally CLI
+//! flags, each layer overriding the last. Produces the [`Settings`] that
+//! `main()` actually runs with.
+
+use std::{net::SocketAddr, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Settings {
+    pub bind_address: SocketAddr,
+    pub dist_dir: PathBuf,
+    pub log_level: String,
+    pub tls_cert: Option<PathBuf>,
+    pub tls_key: Option<PathBuf>,
+    /// Tokens issued to real teams, used to authenticate submission
+    /// requests before they're charged against a rate-limit bucket.
+    pub team_tokens: Vec<String>,
+    /// Burst size (max tokens) of each team's submission rate-limit bucket.
+    pub rate_limit_burst: u32,
+    /// Tokens per second refilled into each team's bucket.
+    pub rate_limit_refill_per_second: f64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            bind_address: "127.0.0.1:3000".parse().unwrap(),
+            dist_dir: PathBuf::from("./dist"),
+            log_level: "info".to_string(),
+            tls_cert: None,
+            tls_key: None,
+            team_tokens: Vec::new(),
+            rate_limit_burst: 10,
+            rate_limit_refill_per_second: 1.0,
+        }
+    }
+}
+
+impl Settings {
+    /// Layers defaults < `config_path` (if present) < `CTF_*` env vars.
+    /// CLI flags are layered on top of the result by the caller, since they
+    /// live on `Cli` in `main`.
+    pub fn load(config_path: &str) -> Result<Settings, config::ConfigError> {
+        let _ = dotenvy::dotenv();
+
+        config::Config::builder()
+            .add_source(config::Config::try_from(&Settings::default())?)
+            .add_source(config::File::with_name(config_path).required(false))
+            .add_source(config::Environment::with_prefix("CTF"))
+            .build()?
+            .try_deserialize()
+    }
+}
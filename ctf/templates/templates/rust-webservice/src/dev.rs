@@ -0,0 +1,159 @@
+//! Developer-only hot-reload support, enabled with `--features dev`.
+//!
+//! Two pieces live here: taking over an already-bound socket from
+//! `systemfd`/`cargo-watch` via `listenfd` (so recompiles don't drop the
+//! port), and a livereload `tower` layer that injects a tiny reconnecting
+//! `<script>` into HTML responses and notifies it over a long-poll endpoint
+//! whenever a file under the watched dist directory changes.
+
+use std::{
+    net::TcpListener as StdTcpListener,
+    path::Path,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use axum::{
+    body::Body,
+    http::{header, Request, Response},
+    response::IntoResponse,
+};
+use futures_util::future::BoxFuture;
+use notify::{RecursiveMode, Watcher};
+use tokio::sync::broadcast;
+use tower::{Layer, Service};
+
+const RELOAD_SCRIPT: &str = r#"<script>
+(function () {
+    function connect() {
+        fetch("/api/dev/reload")
+            .then((res) => {
+                if (res.status === 200) {
+                    location.reload();
+                } else {
+                    connect();
+                }
+            })
+            .catch(() => setTimeout(connect, 1000));
+    }
+    connect();
+})();
+</script>"#;
+
+/// Takes over the socket passed down by `systemfd`/`cargo-watch` when
+/// `LISTEN_FD` is set, falling back to a fresh bind otherwise.
+pub fn take_or_bind_listener(bind_address: std::net::SocketAddr) -> std::io::Result<StdTcpListener> {
+    if let Some(listener) = listenfd::ListenFd::from_env().take_tcp_listener(0)? {
+        tracing::info!("taking over listening socket from listenfd");
+        Ok(listener)
+    } else {
+        StdTcpListener::bind(bind_address)
+    }
+}
+
+/// Spawns a background task that watches `dir` and broadcasts on `tx`
+/// whenever a file under it changes, so the livereload layer can wake up
+/// any long-polling clients.
+pub fn spawn_watcher(dir: impl AsRef<Path>, tx: broadcast::Sender<()>) {
+    let dir = dir.as_ref().to_path_buf();
+    let (watcher_tx, mut watcher_rx) = tokio::sync::mpsc::channel(16);
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if res.is_ok() {
+            let _ = watcher_tx.blocking_send(());
+        }
+    })
+    .expect("failed to create file watcher");
+
+    watcher
+        .watch(&dir, RecursiveMode::Recursive)
+        .unwrap_or_else(|err| tracing::warn!(%err, path = %dir.display(), "could not watch dist dir"));
+
+    tokio::spawn(async move {
+        // Keep the watcher alive for the lifetime of the task.
+        let _watcher = watcher;
+        while watcher_rx.recv().await.is_some() {
+            let _ = tx.send(());
+        }
+    });
+}
+
+/// Long-polls for the next file-change notification. Returns `200 OK` when
+/// a change actually fired so the client reloads, or `204 No Content` when
+/// the 30s poll simply timed out, so the client just reconnects and keeps
+/// waiting instead of reloading on every timeout.
+pub async fn reload_endpoint(
+    axum::extract::Extension(tx): axum::extract::Extension<broadcast::Sender<()>>,
+) -> impl IntoResponse {
+    let mut rx = tx.subscribe();
+    match tokio::time::timeout(Duration::from_secs(30), rx.recv()).await {
+        Ok(_) => axum::http::StatusCode::OK,
+        Err(_) => axum::http::StatusCode::NO_CONTENT,
+    }
+}
+
+/// `tower` layer that injects [`RELOAD_SCRIPT`] just before `</body>` in any
+/// `text/html` response, so pages picked up by `ServeDir` auto-reload.
+#[derive(Clone)]
+pub struct LiveReloadLayer;
+
+impl<S> Layer<S> for LiveReloadLayer {
+    type Service = LiveReloadService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        LiveReloadService { inner }
+    }
+}
+
+#[derive(Clone)]
+pub struct LiveReloadService<S> {
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for LiveReloadService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let mut inner = self.inner.clone();
+        Box::pin(async move {
+            let response = inner.call(req).await?;
+
+            let is_html = response
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|ct| ct.starts_with("text/html"));
+
+            if !is_html {
+                return Ok(response);
+            }
+
+            let (mut parts, body) = response.into_parts();
+            let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+                Ok(bytes) => bytes,
+                Err(_) => return Ok(Response::from_parts(parts, Body::empty())),
+            };
+
+            let mut html = String::from_utf8_lossy(&bytes).into_owned();
+            if let Some(pos) = html.rfind("</body>") {
+                html.insert_str(pos, RELOAD_SCRIPT);
+            } else {
+                html.push_str(RELOAD_SCRIPT);
+            }
+
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Ok(Response::from_parts(parts, Body::from(html)))
+        })
+    }
+}
@@ -1,4 +1,4 @@
-use std::net::SocketAddr;
+use std::{net::SocketAddr, path::PathBuf};
 
 use axum::{Json, Router, http::StatusCode, response::IntoResponse, routing::get};
 
@@ -8,30 +8,204 @@ use serde::Serialize;
 
 use clap::Parser;
 
+mod config;
+#[cfg(feature = "dev")]
+mod dev;
+#[cfg(feature = "mock")]
+mod mock;
+#[cfg(feature = "mock")]
+mod models;
+mod ratelimit;
+mod scoreboard;
+mod ws;
+
+use config::Settings;
+
+use scoreboard::ScoreEvent;
+
+/// Shared state injected into every handler via [`Router::with_state`].
+#[derive(Clone)]
+struct AppState {
+    /// Publishes [`ScoreEvent`]s; the `/api/events` SSE handler subscribes
+    /// to relay them to connected clients.
+    score_tx: tokio::sync::broadcast::Sender<ScoreEvent>,
+    /// Session IDs of currently connected WebSocket clients.
+    presence: ws::Presence,
+    /// Publishes presence messages to every open WebSocket. Submission
+    /// results are sent only to the socket that submitted.
+    presence_tx: tokio::sync::broadcast::Sender<ws::ServerMessage>,
+    /// Per-team token buckets guarding the submission endpoints.
+    limiter: ratelimit::Limiter,
+}
+
 #[derive(Parser)]
 struct Cli {
-    #[clap(short, long, default_value = "127.0.0.1:3000")]
-    bind_address: SocketAddr,
+    /// Path to a layered config file. Defaults are overridden by this file,
+    /// which is in turn overridden by `CTF_*` environment variables and
+    /// then by the flags below.
+    #[clap(long, default_value = "config.toml")]
+    config: String,
+
+    #[clap(short, long)]
+    bind_address: Option<SocketAddr>,
+
+    /// Directory of static files served at the fallback route.
+    #[clap(long)]
+    dist_dir: Option<PathBuf>,
+
+    /// Tracing log level, e.g. `trace`, `debug`, `info`, `warn`, `error`.
+    #[clap(long)]
+    log_level: Option<String>,
+
+    /// TLS certificate (PEM). Serves over HTTPS via `axum-server`/`rustls`
+    /// when set together with `--tls-key`; plain HTTP when neither is set.
+    /// Setting only one of the two is refused at startup.
+    #[clap(long)]
+    tls_cert: Option<PathBuf>,
+
+    /// TLS private key (PEM), paired with `--tls-cert`.
+    #[clap(long)]
+    tls_key: Option<PathBuf>,
+
+    /// Watch the dist directory and livereload connected browsers on change. Intended
+    /// to be run under `systemfd`/`cargo-watch` so the socket survives
+    /// recompiles. Requires the `dev` feature.
+    #[cfg(feature = "dev")]
+    #[clap(long)]
+    watch: bool,
+
+    /// Serve randomized-but-plausible data at `/api/challenges`,
+    /// `/api/teams` and `/api/scoreboard`, so the front end can be developed
+    /// without a real backend. These routes exist only with this flag.
+    /// Requires the `mock` feature.
+    #[cfg(feature = "mock")]
+    #[clap(long)]
+    mock: bool,
+}
+
+impl Cli {
+    /// Applies flags that were actually passed on top of the file/env
+    /// layered `settings`.
+    fn apply_overrides(&self, mut settings: Settings) -> Settings {
+        if let Some(bind_address) = self.bind_address {
+            settings.bind_address = bind_address;
+        }
+        if let Some(dist_dir) = self.dist_dir.clone() {
+            settings.dist_dir = dist_dir;
+        }
+        if let Some(log_level) = self.log_level.clone() {
+            settings.log_level = log_level;
+        }
+        if let Some(tls_cert) = self.tls_cert.clone() {
+            settings.tls_cert = Some(tls_cert);
+        }
+        if let Some(tls_key) = self.tls_key.clone() {
+            settings.tls_key = Some(tls_key);
+        }
+        settings
+    }
 }
 
 #[tokio::main]
 async fn main() {
+    let cli = Cli::parse();
+    let settings = cli.apply_overrides(Settings::load(&cli.config).expect("failed to load configuration"));
+
     // initialize tracing
-    tracing_subscriber::fmt::init();
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::new(&settings.log_level))
+        .init();
 
-    let cli = Cli::parse();
+    let (score_tx, _) = scoreboard::channel();
+    let (presence_tx, _) = ws::channel();
+    let limiter = ratelimit::Limiter::new(
+        &settings.team_tokens,
+        settings.rate_limit_burst,
+        settings.rate_limit_refill_per_second,
+    );
+    tokio::spawn(limiter.clone().evict_idle_periodically());
+    let state = AppState {
+        score_tx,
+        presence: Default::default(),
+        presence_tx,
+        limiter,
+    };
+
+    // submission endpoints are a brute-force target, so they get their own
+    // concurrency cap and per-team rate limit
+    let submission_routes = Router::new()
+        .route("/api/submit", axum::routing::post(ratelimit::submit))
+        .layer(tower::limit::ConcurrencyLimitLayer::new(32))
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            ratelimit::rate_limit,
+        ));
 
     // build our application with a route
     let app = Router::new()
         // `GET /` goes to `root`
         .route("/api/hello", get(hello))
-        .fallback_service(ServeDir::new("./dist"));
+        .route("/api/events", get(scoreboard::events))
+        .route("/api/ws", get(ws::ws_handler))
+        .merge(submission_routes)
+        .with_state(state);
+
+    #[cfg(feature = "mock")]
+    let app = if cli.mock {
+        app.route("/api/challenges", get(mock::mock_challenges))
+            .route("/api/teams", get(mock::mock_teams))
+            .route("/api/scoreboard", get(mock::mock_scoreboard))
+    } else {
+        app
+    };
+
+    // `ServeDir` must be registered before `LiveReloadLayer` is applied:
+    // `Router::layer` only wraps routes/fallbacks that already exist, so a
+    // fallback added afterward would never pass through the layer and the
+    // reload script would never get injected into the pages it serves.
+    let app = app.fallback_service(ServeDir::new(&settings.dist_dir));
+
+    #[cfg(feature = "dev")]
+    let app = if cli.watch {
+        let (reload_tx, _) = tokio::sync::broadcast::channel(16);
+        dev::spawn_watcher(&settings.dist_dir, reload_tx.clone());
+
+        app.route("/api/dev/reload", get(dev::reload_endpoint))
+            .layer(axum::Extension(reload_tx))
+            .layer(dev::LiveReloadLayer)
+    } else {
+        app
+    };
 
     // run our app with hyper, listening globally on port 3000
-    let listener = tokio::net::TcpListener::bind(cli.bind_address)
-        .await
-        .unwrap();
-    axum::serve(listener, app).await.unwrap();
+    match (&settings.tls_cert, &settings.tls_key) {
+        (Some(cert), Some(key)) => {
+            let tls_config = axum_server::tls_rustls::RustlsConfig::from_pem_file(cert, key)
+                .await
+                .expect("failed to load TLS certificate/key");
+            axum_server::bind_rustls(settings.bind_address, tls_config)
+                .serve(app.into_make_service())
+                .await
+                .unwrap();
+        }
+        (Some(_), None) | (None, Some(_)) => {
+            panic!(
+                "tls_cert and tls_key must both be set to serve over TLS; refusing to start with a partial TLS config"
+            );
+        }
+        (None, None) => {
+            #[cfg(feature = "dev")]
+            let listener = tokio::net::TcpListener::from_std(
+                dev::take_or_bind_listener(settings.bind_address).unwrap(),
+            )
+            .unwrap();
+            #[cfg(not(feature = "dev"))]
+            let listener = tokio::net::TcpListener::bind(settings.bind_address)
+                .await
+                .unwrap();
+            axum::serve(listener, app).await.unwrap();
+        }
+    }
 }
 
 // basic handler that responds with a static string
@@ -0,0 +1,81 @@
+//! Randomized-but-plausible data for the `--mock` flag, so front-end work
+//! against the scoreboard/challenge views has something to render before a
+//! real backend exists. The data changes on every request, which lets
+//! authors visually confirm the UI re-fetches and re-renders — a static
+//! fixture wouldn't reveal that.
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::models::{Challenge, ScoreEntry, Team};
+
+const CATEGORIES: &[&str] = &["web", "pwn", "crypto", "rev", "forensics", "misc"];
+const TEAM_NAMES: &[&str] = &["0xDEADBEEF", "null_pointer", "the_rootkits", "sudo_rm_rf", "ctrl_alt_elite"];
+const CHALLENGE_NAMES: &[&str] = &["baby-web", "heap-overflow", "padding-oracle", "unpack-me", "disk-image"];
+
+/// Generates a single randomized instance of `Self`.
+pub trait Mock {
+    fn get_one(rng: &mut impl Rng) -> Self;
+
+    /// Generates `n` instances. The default just calls [`Mock::get_one`]
+    /// repeatedly; override for types that need post-processing, e.g.
+    /// sorting challenges by points.
+    fn get_several(rng: &mut impl Rng, n: usize) -> Vec<Self>
+    where
+        Self: Sized,
+    {
+        (0..n).map(|_| Self::get_one(rng)).collect()
+    }
+}
+
+impl Mock for Challenge {
+    fn get_one(rng: &mut impl Rng) -> Self {
+        Challenge {
+            id: rng.gen_range(1..1000),
+            name: CHALLENGE_NAMES.choose(rng).unwrap().to_string(),
+            category: CATEGORIES.choose(rng).unwrap().to_string(),
+            points: rng.gen_range(1..10) * 50,
+        }
+    }
+
+    fn get_several(rng: &mut impl Rng, n: usize) -> Vec<Self> {
+        let mut challenges: Vec<_> = (0..n).map(|_| Self::get_one(rng)).collect();
+        challenges.sort_by_key(|c| c.points);
+        challenges
+    }
+}
+
+impl Mock for Team {
+    fn get_one(rng: &mut impl Rng) -> Self {
+        Team {
+            id: rng.gen_range(1..1000),
+            name: TEAM_NAMES.choose(rng).unwrap().to_string(),
+        }
+    }
+}
+
+impl Mock for ScoreEntry {
+    fn get_one(rng: &mut impl Rng) -> Self {
+        ScoreEntry {
+            team: TEAM_NAMES.choose(rng).unwrap().to_string(),
+            score: rng.gen_range(0..5000),
+        }
+    }
+
+    fn get_several(rng: &mut impl Rng, n: usize) -> Vec<Self> {
+        let mut entries: Vec<_> = (0..n).map(|_| Self::get_one(rng)).collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.score));
+        entries
+    }
+}
+
+pub async fn mock_challenges() -> axum::Json<Vec<Challenge>> {
+    axum::Json(Challenge::get_several(&mut rand::thread_rng(), 10))
+}
+
+pub async fn mock_teams() -> axum::Json<Vec<Team>> {
+    axum::Json(Team::get_several(&mut rand::thread_rng(), 8))
+}
+
+pub async fn mock_scoreboard() -> axum::Json<Vec<ScoreEntry>> {
+    axum::Json(ScoreEntry::get_several(&mut rand::thread_rng(), 8))
+}
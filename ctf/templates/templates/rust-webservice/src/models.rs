@@ -0,0 +1,23 @@
+//! Core domain types served by the scoreboard/challenge views.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Challenge {
+    pub id: u32,
+    pub name: String,
+    pub category: String,
+    pub points: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Team {
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoreEntry {
+    pub team: String,
+    pub score: u32,
+}
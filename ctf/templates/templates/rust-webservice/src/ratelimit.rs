@@ -0,0 +1,171 @@
+//! Rate limiting and concurrency control for submission endpoints, which are
+//! a natural brute-force target for flag guessing.
+//!
+//! A per-team token bucket lives in a sharded map keyed by team token, so
+//! lock contention between unrelated teams stays low. A background task
+//! periodically evicts idle buckets so memory stays bounded as teams come
+//! and go. [`tower::limit::ConcurrencyLimitLayer`] is layered on top to cap
+//! how many submissions are in flight at once.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::AppState;
+
+const SHARD_COUNT: usize = 16;
+const IDLE_EVICTION: Duration = Duration::from_secs(600);
+
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+    last_used: Instant,
+}
+
+impl TokenBucket {
+    fn new(capacity: u32) -> Self {
+        let now = Instant::now();
+        TokenBucket {
+            tokens: capacity as f64,
+            last_refill: now,
+            last_used: now,
+        }
+    }
+
+    /// Refills based on elapsed time, then tries to take one token.
+    /// Returns `None` on success, or `Some(retry_after)` when empty.
+    fn try_take(&mut self, capacity: u32, refill_per_second: f64) -> Option<Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * refill_per_second).min(capacity as f64);
+        self.last_refill = now;
+        self.last_used = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            let missing = 1.0 - self.tokens;
+            Some(Duration::from_secs_f64(missing / refill_per_second))
+        }
+    }
+}
+
+/// Sharded map of per-team token buckets, keyed by a team token that has
+/// already been authenticated against [`Limiter::valid_team_tokens`].
+#[derive(Clone)]
+pub struct Limiter {
+    shards: Arc<Vec<Mutex<HashMap<String, TokenBucket>>>>,
+    valid_team_tokens: Arc<HashSet<String>>,
+    burst: u32,
+    refill_per_second: f64,
+}
+
+impl Limiter {
+    /// `team_tokens` is the set of tokens issued to real teams (from
+    /// [`crate::config::Settings`]); requests presenting anything else are
+    /// rejected before they ever reach a bucket, so an attacker can't mint
+    /// unlimited fresh buckets by varying the header.
+    pub fn new(team_tokens: &[String], burst: u32, refill_per_second: f64) -> Self {
+        let shards = (0..SHARD_COUNT).map(|_| Mutex::new(HashMap::new())).collect();
+        Limiter {
+            shards: Arc::new(shards),
+            valid_team_tokens: Arc::new(team_tokens.iter().cloned().collect()),
+            burst,
+            refill_per_second,
+        }
+    }
+
+    fn shard_for(&self, team_token: &str) -> &Mutex<HashMap<String, TokenBucket>> {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in team_token.bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        &self.shards[(hash as usize) % self.shards.len()]
+    }
+
+    /// Returns `Ok(None)` if the request is allowed, `Ok(Some(retry_after))`
+    /// if the team's bucket is currently empty, or `Err(())` if `team_token`
+    /// doesn't belong to a known team.
+    fn check(&self, team_token: &str) -> Result<Option<Duration>, ()> {
+        if !self.valid_team_tokens.contains(team_token) {
+            return Err(());
+        }
+
+        let mut shard = self.shard_for(team_token).lock().unwrap();
+        let bucket = shard
+            .entry(team_token.to_string())
+            .or_insert_with(|| TokenBucket::new(self.burst));
+        Ok(bucket.try_take(self.burst, self.refill_per_second))
+    }
+
+    /// Drops buckets that haven't been used in a while, so memory doesn't
+    /// grow unbounded as teams disconnect.
+    pub async fn evict_idle_periodically(self) {
+        let mut interval = tokio::time::interval(Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            for shard in self.shards.iter() {
+                let mut shard = shard.lock().unwrap();
+                shard.retain(|_, bucket| bucket.last_used.elapsed() < IDLE_EVICTION);
+            }
+        }
+    }
+}
+
+/// `axum` middleware applying the per-team token bucket. Teams authenticate
+/// with the `X-Team-Token` header; unrecognized or missing tokens are
+/// rejected outright instead of being given their own bucket, so the limit
+/// can't be defeated by sending a fresh random value per request.
+pub async fn rate_limit(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let team_token = request
+        .headers()
+        .get("x-team-token")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+
+    match state.limiter.check(&team_token) {
+        Ok(None) => next.run(request).await,
+        Ok(Some(retry_after)) => {
+            let mut response = StatusCode::TOO_MANY_REQUESTS.into_response();
+            if let Ok(value) = HeaderValue::from_str(&retry_after.as_secs().max(1).to_string()) {
+                response.headers_mut().insert(header::RETRY_AFTER, value);
+            }
+            response
+        }
+        Err(()) => StatusCode::UNAUTHORIZED.into_response(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SubmitRequest {
+    pub challenge: String,
+    pub flag: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SubmitResponse {
+    pub accepted: bool,
+}
+
+/// `POST /api/submit` — rate-limited and concurrency-limited flag
+/// submission. Challenge flags aren't modeled yet, so every submission is
+/// rejected for now.
+pub async fn submit(Json(request): Json<SubmitRequest>) -> impl IntoResponse {
+    let _ = request.flag;
+    let _ = request.challenge;
+    Json(SubmitResponse { accepted: false })
+}
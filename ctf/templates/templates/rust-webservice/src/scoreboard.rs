@@ -0,0 +1,63 @@
+//! Live scoreboard pub-sub: [`ScoreEvent`]s sent on a broadcast channel are
+//! relayed by the `/api/events` SSE handler to every connected client so the
+//! board updates without polling. No handler in this crate sends on the
+//! channel: submissions are always rejected, so no solve is ever published.
+
+use std::{convert::Infallible, time::Duration};
+
+use axum::{
+    extract::State,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures_util::stream::{Stream, StreamExt};
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+
+use crate::AppState;
+
+/// An update to the scoreboard, broadcast to every subscriber.
+///
+/// Nothing publishes these yet: they'll be emitted by solve-submission
+/// handling once real challenges exist to submit against.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[allow(dead_code)]
+pub enum ScoreEvent {
+    /// A team solved a challenge and gained points.
+    Solve {
+        team: String,
+        challenge: String,
+        points: u32,
+    },
+    /// The first team to solve a given challenge.
+    FirstBlood { team: String, challenge: String },
+}
+
+pub fn channel() -> (broadcast::Sender<ScoreEvent>, broadcast::Receiver<ScoreEvent>) {
+    broadcast::channel(256)
+}
+
+/// `GET /api/events` — streams [`ScoreEvent`]s as they're published.
+///
+/// If a subscriber falls behind and the broadcast channel drops messages for
+/// it, we can't resend what was missed, so instead we emit a `resync` event
+/// telling the client to refetch the full scoreboard.
+pub async fn events(State(state): State<AppState>) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = BroadcastStream::new(state.score_tx.subscribe()).map(|msg| {
+        let event = match msg {
+            Ok(event) => Event::default()
+                .event("score")
+                .json_data(event)
+                .unwrap_or_else(|_| Event::default().event("resync")),
+            Err(_lagged) => Event::default().event("resync"),
+        };
+        Ok(event)
+    });
+
+    Sse::new(stream).keep_alive(
+        KeepAlive::new()
+            .interval(Duration::from_secs(15))
+            .text("keep-alive"),
+    )
+}
@@ -0,0 +1,117 @@
+//! `GET /api/ws` — WebSocket presence and live submission channel.
+//!
+//! Each connection registers its session [`Uuid`] in shared state on
+//! connect and removes it on disconnect, broadcasting a `presence` message
+//! either way so every other connected client can show who's online. Flag
+//! submissions sent over the socket get an instant `result` message back,
+//! instead of players having to poll a REST endpoint.
+
+use std::{collections::HashSet, sync::Arc};
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::IntoResponse,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, Mutex};
+use uuid::Uuid;
+
+use crate::AppState;
+
+/// Connected session IDs, shared across all sockets.
+pub type Presence = Arc<Mutex<HashSet<Uuid>>>;
+
+/// Message sent by a client over the socket.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    Submit {
+        challenge: String,
+        // Accepted on the wire but not checked yet: no real flag store
+        // exists until a challenge backend is wired up.
+        #[allow(dead_code)]
+        flag: String,
+    },
+}
+
+/// Message sent to clients: `Presence` is broadcast to every socket,
+/// `Result` goes only to the socket that submitted.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    Result { challenge: String, accepted: bool },
+    Presence { online: Vec<Uuid> },
+}
+
+pub async fn ws_handler(State(state): State<AppState>, ws: WebSocketUpgrade) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_socket(socket, state))
+}
+
+async fn handle_socket(mut socket: WebSocket, state: AppState) {
+    let id = Uuid::new_v4();
+    let mut presence_rx = state.presence_tx.subscribe();
+
+    broadcast_presence(&state, id, true).await;
+
+    loop {
+        tokio::select! {
+            msg = socket.recv() => {
+                match msg {
+                    Some(Ok(Message::Text(text))) => {
+                        if let Ok(client_msg) = serde_json::from_str::<ClientMessage>(&text) {
+                            handle_client_message(&mut socket, client_msg).await;
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Err(_)) => break,
+                    _ => {}
+                }
+            }
+            Ok(server_msg) = presence_rx.recv() => {
+                let Ok(text) = serde_json::to_string(&server_msg) else { continue };
+                if socket.send(Message::Text(text)).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    broadcast_presence(&state, id, false).await;
+}
+
+async fn handle_client_message(socket: &mut WebSocket, msg: ClientMessage) {
+    match msg {
+        ClientMessage::Submit { challenge, flag: _ } => {
+            // Challenge flags aren't modeled yet, so every submission is
+            // rejected for now; this will be wired up to real challenge
+            // data once it exists.
+            let result = ServerMessage::Result {
+                challenge,
+                accepted: false,
+            };
+            if let Ok(text) = serde_json::to_string(&result) {
+                let _ = socket.send(Message::Text(text)).await;
+            }
+        }
+    }
+}
+
+async fn broadcast_presence(state: &AppState, id: Uuid, connected: bool) {
+    let mut presence = state.presence.lock().await;
+    if connected {
+        presence.insert(id);
+    } else {
+        presence.remove(&id);
+    }
+    let online: Vec<Uuid> = presence.iter().copied().collect();
+    drop(presence);
+
+    let _ = state.presence_tx.send(ServerMessage::Presence { online });
+}
+
+pub fn channel() -> (broadcast::Sender<ServerMessage>, broadcast::Receiver<ServerMessage>) {
+    broadcast::channel(256)
+}